@@ -0,0 +1,173 @@
+//! # Fetch-based HTTP client for exchanging IronShield challenges.
+//!
+//! Wraps the browser `fetch` API so integrators don't have to hand-wire
+//! requests in JavaScript: [`request_challenge`] retrieves a challenge
+//! from a server endpoint, and [`submit_response`] posts a solved
+//! response back. Failures are modeled as a [`RequestError`] before being
+//! converted to an informative `JsValue` for the JavaScript caller.
+
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Request, RequestInit, RequestMode, Response};
+
+use crate::js_challenge::JsIronShieldChallenge;
+use crate::js_response::JsIronShieldChallengeResponse;
+
+/// Header under which a challenge or response may be carried instead of
+/// (or in addition to) the request/response body.
+const IRONSHIELD_HEADER: &str = "X-IronShield-Challenge";
+
+/// Structured failure reasons for the challenge/response exchange.
+enum RequestError {
+    /// The `fetch` call itself failed, e.g. a network error or CORS.
+    Network(String),
+    /// The server responded with a non-2xx status.
+    Server { status: u16, message: String },
+    /// The response body/header could not be decoded into the expected type.
+    Decode(String),
+}
+
+impl RequestError {
+    fn into_js_value(self) -> JsValue {
+        let message = match self {
+            RequestError::Network(msg)              => format!("Network error: {}", msg),
+            RequestError::Server { status, message } => format!("Server returned {}: {}", status, message),
+            RequestError::Decode(msg)                => format!("Failed to decode response: {}", msg),
+        };
+        JsValue::from_str(&message)
+    }
+}
+
+/// Sends `request` and resolves it to a `Response`.
+///
+/// Looks up `fetch` on the global scope rather than `web_sys::window()` so
+/// this also works when the solver (and this client) run off the main
+/// thread in a Web Worker, where there is no `Window`.
+async fn fetch(request: &Request) -> Result<Response, RequestError> {
+    let global   = js_sys::global();
+    let fetch_fn = Reflect::get(&global, &"fetch".into())
+        .ok()
+        .and_then(|f| f.dyn_into::<js_sys::Function>().ok())
+        .ok_or_else(|| RequestError::Network("no global `fetch` function".to_string()))?;
+
+    let promise = fetch_fn
+        .call1(&global, request)
+        .map_err(|e| RequestError::Network(format!("{:?}", e)))?
+        .dyn_into::<js_sys::Promise>()
+        .map_err(|_| RequestError::Network("fetch did not return a Promise".to_string()))?;
+
+    let response_value = JsFuture::from(promise)
+        .await
+        .map_err(|e| RequestError::Network(format!("{:?}", e)))?;
+
+    response_value
+        .dyn_into::<Response>()
+        .map_err(|_| RequestError::Network("fetch did not resolve to a Response".to_string()))
+}
+
+/// Reads the full response body as text.
+async fn response_text(response: &Response) -> Result<String, RequestError> {
+    let text_promise = response.text().map_err(|e| RequestError::Decode(format!("{:?}", e)))?;
+
+    JsFuture::from(text_promise)
+        .await
+        .map_err(|e| RequestError::Decode(format!("{:?}", e)))?
+        .as_string()
+        .ok_or_else(|| RequestError::Decode("response body was not a string".to_string()))
+}
+
+/// Fetches an IronShield challenge from `url`.
+///
+/// Decodes the challenge from the `X-IronShield-Challenge` response
+/// header if present, falling back to parsing the body as JSON.
+///
+/// # Arguments
+/// * `url`: The endpoint to GET the challenge from.
+///
+/// # Returns
+/// * `Result<JsIronShieldChallenge, JsValue>`: The decoded challenge, or
+///                                             an error if the request
+///                                             failed or could not be
+///                                             decoded.
+#[wasm_bindgen]
+pub async fn request_challenge(url: &str) -> Result<JsIronShieldChallenge, JsValue> {
+    request_challenge_inner(url).await.map_err(RequestError::into_js_value)
+}
+
+async fn request_challenge_inner(url: &str) -> Result<JsIronShieldChallenge, RequestError> {
+    let mut init = RequestInit::new();
+    init.method("GET");
+    init.mode(RequestMode::Cors);
+
+    let request = Request::new_with_str_and_init(url, &init)
+        .map_err(|e| RequestError::Network(format!("{:?}", e)))?;
+    request
+        .headers()
+        .set("Accept", "application/json")
+        .map_err(|e| RequestError::Network(format!("{:?}", e)))?;
+
+    let response = fetch(&request).await?;
+    let status   = response.status();
+
+    if !(200..300).contains(&status) {
+        let body = response_text(&response).await?;
+        return Err(RequestError::Server { status, message: body });
+    }
+
+    if let Ok(Some(header)) = response.headers().get(IRONSHIELD_HEADER) {
+        return JsIronShieldChallenge::from_base64url_header(&header)
+            .map_err(|e| RequestError::Decode(format!("{:?}", e)));
+    }
+
+    let body = response_text(&response).await?;
+    JsIronShieldChallenge::from_json(&body).map_err(|e| RequestError::Decode(format!("{:?}", e)))
+}
+
+/// Submits a solved challenge response to `url` via the
+/// `X-IronShield-Challenge` request header.
+///
+/// # Arguments
+/// * `url`:      The endpoint to POST the response to.
+/// * `response`: The solved challenge response.
+///
+/// # Returns
+/// * `Result<JsValue, JsValue>`: The server's reply body, or an error if
+///                               the request failed or was rejected.
+#[wasm_bindgen]
+pub async fn submit_response(
+    url:      &str,
+    response: &JsIronShieldChallengeResponse,
+) -> Result<JsValue, JsValue> {
+    submit_response_inner(url, response).await.map_err(RequestError::into_js_value)
+}
+
+async fn submit_response_inner(
+    url:      &str,
+    response: &JsIronShieldChallengeResponse,
+) -> Result<JsValue, RequestError> {
+    let mut init = RequestInit::new();
+    init.method("POST");
+    init.mode(RequestMode::Cors);
+
+    let request = Request::new_with_str_and_init(url, &init)
+        .map_err(|e| RequestError::Network(format!("{:?}", e)))?;
+
+    // The solution rides entirely in the `IRONSHIELD_HEADER` header; this
+    // request carries no body, so no `Content-Type` is set.
+    request
+        .headers()
+        .set(IRONSHIELD_HEADER, &response.to_base64url_header())
+        .map_err(|e| RequestError::Network(format!("{:?}", e)))?;
+
+    let http_response = fetch(&request).await?;
+    let status         = http_response.status();
+    let body           = response_text(&http_response).await?;
+
+    if !(200..300).contains(&status) {
+        return Err(RequestError::Server { status, message: body });
+    }
+
+    Ok(JsValue::from_str(&body))
+}
@@ -131,4 +131,28 @@ impl JsIronShieldChallenge {
     pub fn challenge_signature_hex(&self) -> String {
         hex::encode(self.inner.challenge_signature)
     }
+
+    /// Verifies `challenge_signature` against `public_key` before a solver
+    /// spends CPU on this challenge.
+    ///
+    /// Delegates to `ironshield_types`' own Ed25519 verification so this
+    /// binding can never drift from the message encoding the server
+    /// actually signs against.
+    ///
+    /// # Returns
+    /// * `bool`: `true` if the signature is valid, `false` if it is
+    ///           forged, corrupted, or `public_key` is malformed.
+    #[wasm_bindgen]
+    pub fn verify_signature(&self) -> bool {
+        self.inner.verify_signature()
+    }
+}
+
+impl JsIronShieldChallenge {
+    /// Gives other modules in this crate (e.g. the solver) direct access
+    /// to the wrapped `IronShieldChallenge` instead of re-decoding the
+    /// hex getters meant for JavaScript callers.
+    pub(crate) fn inner_ref(&self) -> &IronShieldChallenge {
+        &self.inner
+    }
 }
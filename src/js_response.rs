@@ -122,4 +122,14 @@ impl JsIronShieldChallengeResponse {
     pub fn solution(&self) -> i64 {
         self.inner.solution
     }
+}
+
+impl JsIronShieldChallengeResponse {
+    /// Builds a response directly from raw parts, skipping the hex
+    /// decoding and length check `new` performs for JavaScript callers.
+    ///
+    /// Used by the solver, which already holds the signature as bytes.
+    pub(crate) fn from_parts(challenge_signature: [u8; 64], solution: i64) -> Self {
+        Self { inner: IronShieldChallengeResponse::new(challenge_signature, solution) }
+    }
 }
\ No newline at end of file
@@ -3,6 +3,10 @@
 // Suppress warnings from wasm-bindgen internals during ABI transition
 #![allow(wasm_c_abi)]
 
+mod http;
+mod js_challenge;
+mod js_response;
+mod solver;
 mod wasm_compat;
 
 use wasm_bindgen::prelude::*;
@@ -0,0 +1,287 @@
+//! # Proof-of-work solver for IronShield challenges.
+//!
+//! When the browser reports `mode == "wasm-mt"` the nonce search space is
+//! partitioned across the wasm-bindgen-rayon thread pool (worker `i` of
+//! `n` tries nonces `i, i + n, i + 2n, ...`); otherwise a single-threaded
+//! scan is used. The first worker to find a nonce whose challenge hash is
+//! `<= challenge_param` signals the others to stop.
+
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AbortSignal, Performance};
+
+#[cfg(all(feature = "threading", not(feature = "no-threading")))]
+use rayon::prelude::*;
+
+use ironshield_types::IronShieldChallenge;
+
+use crate::js_challenge::JsIronShieldChallenge;
+use crate::js_response::JsIronShieldChallengeResponse;
+
+/// Sentinel stored in `winner` until a worker finds a valid nonce.
+const NO_SOLUTION: i64 = -1;
+
+/// Number of candidate nonces scanned (in total, across all workers)
+/// between progress callbacks and abort checks in
+/// [`solve_challenge_with_progress`].
+const PROGRESS_INTERVAL: i64 = 2_000_000;
+
+/// Scans nonces `start, start + stride, start + 2 * stride, ...` until one
+/// meets `challenge`'s difficulty target (via `ironshield_types`' own
+/// hashing, so this can never drift from how the server verifies a
+/// solution) or another worker already has (`found`).
+///
+/// # Arguments
+/// * `challenge`: The challenge being solved.
+/// * `start`:     This worker's starting candidate nonce.
+/// * `stride`:    The total number of workers scanning in parallel.
+/// * `found`:     Set to `true` by whichever worker finds a solution.
+/// * `winner`:    Stores the winning worker's candidate nonce.
+fn scan_worker(
+    challenge: &IronShieldChallenge,
+    start:     i64,
+    stride:    i64,
+    found:     &AtomicBool,
+    winner:    &AtomicI64,
+) {
+    let mut candidate = start;
+    while !found.load(Ordering::Relaxed) {
+        if challenge.verify_solution(candidate) {
+            if !found.swap(true, Ordering::SeqCst) {
+                winner.store(candidate, Ordering::SeqCst);
+            }
+            return;
+        }
+        candidate += stride;
+    }
+}
+
+/// Scans at most `count` nonces `start, start + stride, ...` and returns
+/// the first one that meets `challenge`'s difficulty target, or `None` if
+/// the batch is exhausted without a solution.
+fn scan_range(challenge: &IronShieldChallenge, start: i64, stride: i64, count: i64) -> Option<i64> {
+    let mut candidate = start;
+    for _ in 0..count {
+        if challenge.verify_solution(candidate) {
+            return Some(candidate);
+        }
+        candidate += stride;
+    }
+    None
+}
+
+/// Looks up `setTimeout` on the global scope, which (unlike
+/// `web_sys::window()`) resolves both on the main thread and inside a
+/// `WorkerGlobalScope`, where the solve is expected to actually run.
+fn global_set_timeout() -> Option<js_sys::Function> {
+    Reflect::get(&js_sys::global(), &"setTimeout".into())
+        .ok()
+        .and_then(|f| f.dyn_into::<js_sys::Function>().ok())
+}
+
+/// Looks up `performance` on the global scope, available on both `Window`
+/// and `WorkerGlobalScope`.
+fn global_performance() -> Option<Performance> {
+    Reflect::get(&js_sys::global(), &"performance".into())
+        .ok()
+        .and_then(|p| p.dyn_into::<Performance>().ok())
+}
+
+/// Yields control back to the event loop by awaiting a `setTimeout(0)`.
+///
+/// Without this, an `async fn` whose loop body never `.await`s runs to
+/// completion on its first poll and blocks the calling thread for the
+/// whole solve: the page can't repaint to update `on_progress`'s
+/// spinner/ETA, and an `AbortSignal` toggled from JS never has a chance
+/// to be observed as `aborted()`. If no `setTimeout` is available at all,
+/// resolve immediately rather than leaving the promise (and the solve)
+/// hanging forever.
+async fn yield_to_event_loop() {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        match global_set_timeout() {
+            Some(set_timeout) => {
+                let _ = set_timeout.call2(&JsValue::NULL, &resolve, &JsValue::from_f64(0.0));
+            }
+            None => {
+                let _ = resolve.call0(&JsValue::NULL);
+            }
+        }
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+/// Scans the nonce space single-threaded, starting at `0`.
+fn solve_single(challenge: &IronShieldChallenge) -> i64 {
+    let found  = AtomicBool::new(false);
+    let winner = AtomicI64::new(NO_SOLUTION);
+
+    scan_worker(challenge, 0, 1, &found, &winner);
+
+    winner.load(Ordering::SeqCst)
+}
+
+/// Partitions the nonce space across `thread_count` rayon workers on the
+/// wasm-bindgen-rayon thread pool.
+#[cfg(all(feature = "threading", not(feature = "no-threading")))]
+fn solve_parallel(challenge: &IronShieldChallenge, thread_count: i64) -> i64 {
+    let found  = AtomicBool::new(false);
+    let winner = AtomicI64::new(NO_SOLUTION);
+
+    (0..thread_count).into_par_iter().for_each(|worker| {
+        scan_worker(challenge, worker, thread_count, &found, &winner);
+    });
+
+    winner.load(Ordering::SeqCst)
+}
+
+/// Solves an IronShield proof-of-work challenge.
+///
+/// # Arguments
+/// * `challenge`: The challenge to solve, as received from the server.
+///
+/// # Returns
+/// * `Result<JsIronShieldChallengeResponse, JsValue>`: The winning
+///                                                     solution, ready to
+///                                                     submit back to the
+///                                                     server, or an error
+///                                                     if no solution was
+///                                                     found.
+#[wasm_bindgen]
+pub async fn solve_challenge(
+    challenge: &JsIronShieldChallenge,
+) -> Result<JsIronShieldChallengeResponse, JsValue> {
+    if !challenge.verify_signature() {
+        return Err(JsValue::from_str("Challenge signature verification failed; refusing to solve."));
+    }
+
+    let inner               = challenge.inner_ref();
+    let challenge_signature = inner.challenge_signature;
+
+    let compatibility = crate::wasm_compat::check_wasm_compatibility(&crate::wasm_compat::CompatibilityRequirements::new());
+
+    let solution = {
+        #[cfg(all(feature = "threading", not(feature = "no-threading")))]
+        {
+            if compatibility.mode == "wasm-mt" {
+                solve_parallel(inner, compatibility.thread_count.max(1) as i64)
+            } else {
+                solve_single(inner)
+            }
+        }
+
+        #[cfg(not(all(feature = "threading", not(feature = "no-threading"))))]
+        {
+            solve_single(inner)
+        }
+    };
+
+    if solution == NO_SOLUTION {
+        return Err(JsValue::from_str("Failed to find a solution nonce."));
+    }
+
+    Ok(JsIronShieldChallengeResponse::from_parts(challenge_signature, solution))
+}
+
+/// Solves an IronShield proof-of-work challenge, reporting progress and
+/// accepting cancellation.
+///
+/// The nonce space is scanned in batches of [`PROGRESS_INTERVAL`]
+/// candidates (parallelized across workers the same way as
+/// [`solve_challenge`] when `mode == "wasm-mt"`). Between batches,
+/// `on_progress` is invoked with the current hash rate (hashes/sec) and
+/// total attempts so far, and `abort_signal`, if given, is checked to
+/// cancel the solve cleanly.
+///
+/// # Arguments
+/// * `challenge`:     The challenge to solve, as received from the server.
+/// * `on_progress`:   Called as `(hash_rate: f64, attempts: f64)` after
+///                    every batch that ends without a solution.
+/// * `abort_signal`:  Checked after every batch; solving stops as soon as
+///                    `aborted()` is `true`.
+///
+/// # Returns
+/// * `Result<JsIronShieldChallengeResponse, JsValue>`: The winning
+///                                                     solution, or an
+///                                                     error if the
+///                                                     signature check
+///                                                     fails or the solve
+///                                                     was aborted.
+#[wasm_bindgen]
+pub async fn solve_challenge_with_progress(
+    challenge:     &JsIronShieldChallenge,
+    on_progress:   js_sys::Function,
+    abort_signal:  Option<AbortSignal>,
+) -> Result<JsIronShieldChallengeResponse, JsValue> {
+    if !challenge.verify_signature() {
+        return Err(JsValue::from_str("Challenge signature verification failed; refusing to solve."));
+    }
+
+    let inner               = challenge.inner_ref();
+    let challenge_signature = inner.challenge_signature;
+
+    let compatibility = crate::wasm_compat::check_wasm_compatibility(&crate::wasm_compat::CompatibilityRequirements::new());
+    let stride = {
+        #[cfg(all(feature = "threading", not(feature = "no-threading")))]
+        {
+            if compatibility.mode == "wasm-mt" { compatibility.thread_count.max(1) as i64 } else { 1 }
+        }
+        #[cfg(not(all(feature = "threading", not(feature = "no-threading"))))]
+        {
+            1
+        }
+    };
+
+    let performance  = global_performance();
+    let start_time   = performance.as_ref().map(|p| p.now()).unwrap_or(0.0);
+    let mut attempts: i64 = 0;
+    let mut base:     i64 = 0;
+
+    loop {
+        if let Some(signal) = &abort_signal {
+            if signal.aborted() {
+                return Err(JsValue::from_str("Solve aborted."));
+            }
+        }
+
+        let per_worker_batch = (PROGRESS_INTERVAL / stride).max(1);
+
+        let found = {
+            #[cfg(all(feature = "threading", not(feature = "no-threading")))]
+            {
+                if stride > 1 {
+                    (0..stride).into_par_iter().find_map_any(|worker| {
+                        scan_range(inner, base + worker, stride, per_worker_batch)
+                    })
+                } else {
+                    scan_range(inner, base, 1, per_worker_batch)
+                }
+            }
+            #[cfg(not(all(feature = "threading", not(feature = "no-threading"))))]
+            {
+                scan_range(inner, base, 1, per_worker_batch)
+            }
+        };
+
+        attempts += per_worker_batch * stride;
+
+        if let Some(solution) = found {
+            return Ok(JsIronShieldChallengeResponse::from_parts(challenge_signature, solution));
+        }
+
+        base += per_worker_batch * stride;
+
+        let elapsed_secs = performance
+            .as_ref()
+            .map(|p| ((p.now() - start_time) / 1000.0).max(0.001))
+            .unwrap_or(1.0);
+        let hash_rate = attempts as f64 / elapsed_secs;
+
+        let _ = on_progress.call2(&JsValue::NULL, &JsValue::from_f64(hash_rate), &JsValue::from_f64(attempts as f64));
+
+        yield_to_event_loop().await;
+    }
+}
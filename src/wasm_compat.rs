@@ -1,28 +1,86 @@
 //! # WASM Compatibility Functions and Headers.
 
-use js_sys::{global, Reflect};
+use js_sys::{global, Function, Reflect, Uint8Array};
 use serde::{Serialize, Deserialize};
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 use web_sys::{console, window};
 
+/// Minimal module containing an `i8x16.popcnt` over an `i8x16.splat`;
+/// `WebAssembly.validate` accepts it only when the engine implements the
+/// fixed-width SIMD proposal.
+const SIMD_TEST_MODULE: &[u8] = &[
+    0, 97, 115, 109, 1, 0, 0, 0, 1, 5, 1, 96, 0, 1, 123, 3, 2, 1, 0, 10, 10, 1, 8, 0, 65, 0, 253,
+    15, 253, 98, 11,
+];
+
+/// Minimal module containing a `memory.copy`; `WebAssembly.validate`
+/// accepts it only when the engine implements the bulk-memory proposal.
+const BULK_MEMORY_TEST_MODULE: &[u8] = &[
+    0, 97, 115, 109, 1, 0, 0, 0, 1, 4, 1, 96, 0, 0, 3, 2, 1, 0, 5, 3, 1, 0, 1, 10, 14, 1, 12, 0,
+    65, 0, 65, 0, 65, 0, 252, 10, 0, 0, 11,
+];
+
+/// Capabilities a caller would like [`check_wasm_compatibility`] to probe
+/// for. Defaults to requesting every optional capability.
+///
+/// * `want_threads`:     Whether to probe for multithreading support.
+/// * `want_simd`:        Whether to probe for SIMD support.
+/// * `want_bulk_memory`: Whether to probe for bulk-memory support.
+#[wasm_bindgen]
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatibilityRequirements {
+    pub want_threads:     bool,
+    pub want_simd:        bool,
+    pub want_bulk_memory: bool,
+}
+
+#[wasm_bindgen]
+impl CompatibilityRequirements {
+    /// Creates a requirements object requesting every optional capability.
+    pub fn new() -> Self {
+        Self {
+            want_threads:     true,
+            want_simd:        true,
+            want_bulk_memory: true,
+        }
+    }
+}
+
+impl Default for CompatibilityRequirements {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Represents the browser's WebAssembly compatibility status
 /// as a JavaScript object.
 ///
-/// * `mode`:                   The execution mode to use based 
+/// * `mode`:                   The execution mode to use based
 ///                             on the browser's capabilities.
 ///                             `mode` will be `javascript` if
 ///                             WebAssembly is not supported,
 ///                             `wasm` if WebAssembly is supported,
 ///                             or `wasm-mt` if WebAssembly with
 ///                             multithreading is supported.
-/// * `supports_wasm`:          Indicates if the browser supports 
+/// * `supports_wasm`:          Indicates if the browser supports
 ///                             WebAssembly.
 /// * `supports_threads`:       Indicates if the browser supports
-///                             WebAssembly (multi-)threads.
+///                             WebAssembly (multi-)threads. Requires
+///                             cross-origin isolation in addition to
+///                             `SharedArrayBuffer`.
 /// * `supports_shared_memory`: Indicates if the browser supports
 ///                             SharedArrayBuffer.
-/// * `thread_count`:           The number of logical processors 
+/// * `supports_simd`:          Indicates if the browser supports
+///                             WebAssembly fixed-width SIMD.
+/// * `supports_bulk_memory`:   Indicates if the browser supports
+///                             WebAssembly bulk-memory operations.
+/// * `thread_count`:           The number of logical processors
 ///                             available.
+/// * `reasons`:                Human-readable explanations for any
+///                             requested capability that could not be
+///                             satisfied.
 #[wasm_bindgen]
 #[repr(C)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,7 +90,11 @@ pub struct WasmCompatibility {
     pub supports_wasm:          bool,
     pub supports_shared_memory: bool,
     pub supports_threads:       bool,
+    pub supports_simd:          bool,
+    pub supports_bulk_memory:   bool,
     pub thread_count:           u32,
+    #[wasm_bindgen(skip)]
+    pub reasons:                Vec<String>,
 }
 
 #[wasm_bindgen]
@@ -46,9 +108,21 @@ impl WasmCompatibility {
             supports_wasm:          false,
             supports_threads:       false,
             supports_shared_memory: false,
+            supports_simd:          false,
+            supports_bulk_memory:   false,
             thread_count:           1,
+            reasons:                Vec::new(),
         }
     }
+
+    /// # Returns
+    /// * `Vec<String>`: Human-readable explanations for any requested
+    ///                  capability that could not be satisfied (e.g.
+    ///                  "threads unavailable: not cross-origin isolated").
+    #[wasm_bindgen(getter)]
+    pub fn reasons(&self) -> Vec<String> {
+        self.reasons.clone()
+    }
 }
 
 /// # Returns
@@ -65,6 +139,17 @@ fn is_shared_array_buffer_supported() -> bool {
     Reflect::has(&obj, &"SharedArrayBuffer".into()).unwrap_or(false)
 }
 
+/// # Returns
+/// * `bool`: `true` if `globalThis.crossOriginIsolated` is set, `false`
+///           otherwise. Real multithreading needs the page to be
+///           cross-origin isolated, not just `SharedArrayBuffer` support.
+fn is_cross_origin_isolated() -> bool {
+    Reflect::get(&global(), &"crossOriginIsolated".into())
+        .ok()
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
 /// # Returns
 /// * `i32`: The number of logical processors available.
 ///          Returns 1 if the count cannot be determined.
@@ -74,41 +159,99 @@ fn get_hardware_concurrency() -> u32 {
         .unwrap_or(1.0) as u32
 }
 
+/// Feature-detects a WebAssembly proposal by asking `WebAssembly.validate`
+/// to accept a tiny module that only validates under that proposal.
+///
+/// # Returns
+/// * `bool`: `true` if `WebAssembly.validate` accepted `test_module`.
+fn validate_test_module(test_module: &[u8]) -> bool {
+    let wasm_global = match Reflect::get(&global(), &"WebAssembly".into()) {
+        Ok(value) => value,
+        Err(_)    => return false,
+    };
+    let validate_fn = match Reflect::get(&wasm_global, &"validate".into()).and_then(|f| f.dyn_into::<Function>()) {
+        Ok(f)  => f,
+        Err(_) => return false,
+    };
+
+    let bytes = Uint8Array::from(test_module);
+    validate_fn
+        .call1(&wasm_global, &bytes)
+        .ok()
+        .and_then(|result| result.as_bool())
+        .unwrap_or(false)
+}
+
 /// # Returns
 /// * `bool`: `true` if the client supports multithreading with WebAssembly,
-///           `false` otherwise.
-fn check_multithreading_support() -> bool {
-    is_wasm_supported() && is_shared_array_buffer_supported() && get_hardware_concurrency() > 2
+///           `false` otherwise. Pushes a human-readable reason onto
+///           `reasons` for the first unmet requirement found.
+fn check_multithreading_support(reasons: &mut Vec<String>) -> bool {
+    if !is_shared_array_buffer_supported() {
+        reasons.push("threads unavailable: SharedArrayBuffer is not supported.".to_string());
+        return false;
+    }
+    if !is_cross_origin_isolated() {
+        reasons.push("threads unavailable: not cross-origin isolated.".to_string());
+        return false;
+    }
+    if get_hardware_concurrency() <= 2 {
+        reasons.push("threads unavailable: fewer than 3 logical processors detected.".to_string());
+        return false;
+    }
+
+    true
 }
 
-/// Checks the WebAssembly compatibility of the current browser
-/// and returns an object containing the compatibility status.
+/// Checks the WebAssembly compatibility of the current browser against
+/// `requirements` and returns an object containing the compatibility
+/// status, including a `reasons` explaining any capability that was
+/// requested but could not be satisfied.
+///
+/// # Arguments
+/// * `requirements`: Which optional capabilities to probe for.
 ///
 /// # Returns
 /// * `WasmCompatibility`: An object containing the WebAssembly compatibility status.
 #[wasm_bindgen]
-pub fn check_wasm_compatibility() -> WasmCompatibility {
+pub fn check_wasm_compatibility(requirements: &CompatibilityRequirements) -> WasmCompatibility {
     let mut compatibility = WasmCompatibility::new();
 
-    // If WebAssembly is not supported, log a warning and return 
+    // If WebAssembly is not supported, log a warning and return
     // the default compatibility object provided by `WasmCompatibility::new()`.
     // Mode is already set to "javascript" by default.
     if !is_wasm_supported() {
+        compatibility.reasons.push("WebAssembly is not supported in this browser.".to_string());
         console::warn_1(&"WebAssembly is not supported in this browser.".into());
         return compatibility;
     }
 
     compatibility.supports_wasm          = true;
     compatibility.supports_shared_memory = is_shared_array_buffer_supported();
-    compatibility.supports_threads       = check_multithreading_support();
     compatibility.thread_count           = get_hardware_concurrency();
 
-    if compatibility.supports_threads {
-        compatibility.mode = "wasm-mt".to_string();
-    } else {
-        compatibility.mode = "wasm".to_string();
+    if requirements.want_threads {
+        compatibility.supports_threads = check_multithreading_support(&mut compatibility.reasons);
+    }
+    if requirements.want_simd {
+        compatibility.supports_simd = validate_test_module(SIMD_TEST_MODULE);
+        if !compatibility.supports_simd {
+            compatibility.reasons.push("SIMD unavailable: WebAssembly.validate rejected the SIMD test module.".to_string());
+        }
+    }
+    if requirements.want_bulk_memory {
+        compatibility.supports_bulk_memory = validate_test_module(BULK_MEMORY_TEST_MODULE);
+        if !compatibility.supports_bulk_memory {
+            compatibility.reasons.push("Bulk memory unavailable: WebAssembly.validate rejected the bulk-memory test module.".to_string());
+        }
     }
 
+    compatibility.mode = if compatibility.supports_threads {
+        "wasm-mt".to_string()
+    } else {
+        "wasm".to_string()
+    };
+
     compatibility
 }
 
@@ -116,7 +259,7 @@ pub fn check_wasm_compatibility() -> WasmCompatibility {
 /// * `String`: A JSON string representation of the WebAssembly compatibility status.
 #[wasm_bindgen]
 pub fn get_wasm_compatibility() -> String {
-    let compatibility = check_wasm_compatibility();
+    let compatibility = check_wasm_compatibility(&CompatibilityRequirements::new());
     serde_json::to_string(&compatibility)
         .unwrap_or_else(|_| "{\"error\": \"Failed to serialize compatibility data\"}".to_string())
-}
\ No newline at end of file
+}